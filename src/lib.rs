@@ -9,11 +9,13 @@
 extern crate xml;
 
 use std::borrow::{Borrow, Cow};
+use std::collections::{HashMap, HashSet};
 use std::io::{Read, Write};
 use std::fmt;
 
+use xml::attribute::OwnedAttribute;
 use xml::name::OwnedName;
-use xml::reader::{self, EventReader};
+use xml::reader::{self, EventReader, ParserConfig};
 use xml::reader::XmlEvent as ReaderEvent;
 use xml::writer::{self, EventWriter, XmlEvent, EmitterConfig};
 
@@ -22,10 +24,40 @@ use xml::writer::{self, EventWriter, XmlEvent, EmitterConfig};
 pub struct Element<'a> {
     /// The name of the element.
     pub name: Name<'a>,
+    /// The attributes attached to the element.
+    pub attributes: Vec<Attribute<'a>>,
     /// The content of the iterator.
     pub content: Vec<ElemContent<'a>>,
 }
 
+/// A representation of an XML attribute, e.g. `xsi:type="xsd:string"`.
+#[derive(Debug)]
+pub struct Attribute<'a> {
+    /// The name of the attribute.
+    pub name: Name<'a>,
+    /// The value of the attribute.
+    pub value: Cow<'a, str>,
+}
+
+impl<'a> Attribute<'a> {
+    /// Creates a new `Attribute` with the given name and value.
+    pub fn new<T: Into<Cow<'a, str>>>(name: Name<'a>, value: T) -> Attribute<'a> {
+        Attribute {
+            name,
+            value: value.into(),
+        }
+    }
+}
+
+impl From<OwnedAttribute> for Attribute<'static> {
+    fn from(from: OwnedAttribute) -> Attribute<'static> {
+        Attribute {
+            name: from.name.into(),
+            value: from.value.into(),
+        }
+    }
+}
+
 /// A representation of the name of an XML element.
 #[derive(Debug)]
 pub struct Name<'a> {
@@ -100,6 +132,15 @@ pub enum ElemContent<'a> {
     Text(Cow<'a, str>),
     /// A child element.
     Child(Element<'a>),
+    /// A `CDATA` section, e.g. `<![CDATA[some raw markup]]>`.
+    ///
+    /// Unlike `Text`, this content is not entity-escaped when serialized.
+    CData(Cow<'a, str>),
+    /// A comment, e.g. `<!-- some comment -->`.
+    ///
+    /// The surrounding space that `xml-rs` always writes around a comment's body is stripped when
+    /// parsing, so this holds the comment's content without that padding.
+    Comment(Cow<'a, str>),
 }
 
 /// Helper trait to provide a generalized conversion from a given struct to an `Element`.
@@ -117,11 +158,33 @@ pub trait FromXml: Sized {
     fn from_xml(&Element) -> Result<Self, Self::Error>;
 }
 
+/// Configuration controlling how an `Element` is written out by `Element::into_stream_with_config`.
+#[derive(Debug, Default)]
+pub struct WriterConfig {
+    collapse_namespaces: bool,
+}
+
+impl WriterConfig {
+    /// Creates a new `WriterConfig` with the default serialization behavior, i.e. the same
+    /// behavior as `Element::into_stream`.
+    pub fn new() -> WriterConfig {
+        WriterConfig::default()
+    }
+
+    /// Sets whether repeated namespaces should be collapsed into a single set of declarations on
+    /// the root element, rather than re-declared on every element that uses them.
+    pub fn collapse_namespaces(mut self, collapse: bool) -> WriterConfig {
+        self.collapse_namespaces = collapse;
+        self
+    }
+}
+
 impl<'a> Element<'a> {
     /// Create an empty `Element` with no namespace in its name.
     pub fn new_no_ns<T: Into<Cow<'a, str>>>(name: T) -> Element<'a> {
         Element {
             name: Name::new_no_ns(name),
+            attributes: Vec::new(),
             content: Vec::new(),
         }
     }
@@ -133,6 +196,7 @@ impl<'a> Element<'a> {
     {
         Element {
             name: Name::new_default_ns(name, ns),
+            attributes: Vec::new(),
             content: Vec::new(),
         }
     }
@@ -145,13 +209,15 @@ impl<'a> Element<'a> {
     {
         Element {
             name: Name::new(name, ns, prefix),
+            attributes: Vec::new(),
             content: Vec::new(),
         }
     }
 
     ///Reads an `Element` from the given stream.
     pub fn from_stream<R: Read>(stream: R) -> reader::Result<Element<'static>> {
-        let reader = EventReader::new(stream);
+        let config = ParserConfig::new().ignore_comments(false);
+        let reader = EventReader::new_with_config(stream, config);
 
         let mut elem_stack = Vec::<Element<'static>>::new();
         let mut ret = None;
@@ -159,40 +225,9 @@ impl<'a> Element<'a> {
         for event in reader {
             let event = event?;
 
-            match event {
-                ReaderEvent::StartElement { name, .. } => {
-                    //NOTE: if/when i support attributes, that .. is hiding an `attributes` field
-                    let elem = Element {
-                        name: name.into(),
-                        content: vec![],
-                    };
-                    elem_stack.push(elem);
-                }
-                ReaderEvent::EndElement { name } => {
-                    let mut child = None;
-                    let name: Name = name.into();
-                    for i in (0..elem_stack.len()).rev() {
-                        if elem_stack[i].name == name {
-                            child = Some(elem_stack.remove(i));
-                            break;
-                        }
-                    }
-
-                    if let Some(child) = child {
-                        if let Some(head) = elem_stack.last_mut() {
-                            head.push_child(child);
-                        } else {
-                            assert!(ret.is_none());
-                            ret = Some(child);
-                        }
-                    }
-                }
-                ReaderEvent::Characters(text) => {
-                    if let Some(head) = elem_stack.last_mut() {
-                        head.push_text(text);
-                    }
-                }
-                _ => {}
+            if let Some(finished) = close_out_stack(event, &mut elem_stack) {
+                assert!(ret.is_none());
+                ret = Some(finished);
             }
         }
 
@@ -221,23 +256,68 @@ impl<'a> Element<'a> {
         self.content.push(ElemContent::Child(child));
     }
 
+    /// Add the given `CDATA` section to the `Element`.
+    pub fn push_cdata<T: Into<Cow<'a, str>>>(&mut self, content: T) {
+        self.content.push(ElemContent::CData(content.into()));
+    }
+
+    /// Add the given comment to the `Element`.
+    pub fn push_comment<T: Into<Cow<'a, str>>>(&mut self, content: T) {
+        self.content.push(ElemContent::Comment(content.into()));
+    }
+
+    /// Add the given attribute to the `Element`.
+    pub fn push_attr<T: Into<Cow<'a, str>>>(&mut self, name: Name<'a>, value: T) {
+        self.attributes.push(Attribute::new(name, value));
+    }
+
+    /// Returns the value of the first attribute matching the given local name and namespace.
+    pub fn get_attr(&self, local_name: &str, namespace: Option<&str>) -> Option<&str> {
+        self.attributes.iter()
+            .find(|attr| {
+                attr.name.local_name.borrow() as &str == local_name
+                    && attr.name.namespace.as_ref().map(|ns| ns.borrow() as &str) == namespace
+            })
+            .map(|attr| attr.value.borrow())
+    }
+
     /// Serialize this `Element` to the given writer.
     fn serialize<W: Write>(&self, sink: &mut EventWriter<W>) -> writer::Result<()> {
-        match (&self.name.namespace, &self.name.prefix) {
-            (&Some(ref ns), &Some(ref prefix)) => {
-                let full_name = format!("{}:{}", prefix, self.name.local_name);
-                sink.write(XmlEvent::start_element(&*full_name)
-                                    .ns(prefix.borrow(), ns.borrow()))?;
+        let full_name = self.name.prefix.as_ref()
+            .map(|prefix| format!("{}:{}", prefix, self.name.local_name));
+
+        let mut elem = match (&self.name.namespace, &full_name) {
+            (&Some(ref ns), &Some(ref full_name)) => {
+                XmlEvent::start_element(full_name.as_str())
+                    .ns(self.name.prefix.as_ref().unwrap().borrow(), ns.borrow())
             },
             (&Some(ref ns), &None) => {
-                sink.write(XmlEvent::start_element(self.name.local_name.borrow())
-                                    .default_ns(ns.borrow()))?;
+                XmlEvent::start_element(self.name.local_name.borrow()).default_ns(ns.borrow())
             },
-            _ => {
-                sink.write(XmlEvent::start_element(self.name.local_name.borrow()))?;
+            (&None, _) => {
+                XmlEvent::start_element(self.name.local_name.borrow())
+            }
+        };
+
+        for attr in &self.attributes {
+            if let (&Some(ref prefix), &Some(ref ns)) = (&attr.name.prefix, &attr.name.namespace) {
+                elem = elem.ns(prefix.borrow() as &str, ns.borrow() as &str);
             }
         }
 
+        let attr_names: Vec<String> = self.attributes.iter()
+            .map(|attr| match &attr.name.prefix {
+                &Some(ref prefix) => format!("{}:{}", prefix, attr.name.local_name),
+                &None => attr.name.local_name.to_string(),
+            })
+            .collect();
+
+        for (attr, full_name) in self.attributes.iter().zip(attr_names.iter()) {
+            elem = elem.attr(full_name.as_str(), attr.value.borrow());
+        }
+
+        sink.write(elem)?;
+
         for item in &self.content {
             match item {
                 &ElemContent::Text(ref text) => {
@@ -246,6 +326,12 @@ impl<'a> Element<'a> {
                 &ElemContent::Child(ref child) => {
                     child.serialize(sink)?;
                 },
+                &ElemContent::CData(ref text) => {
+                    sink.write(XmlEvent::cdata(text.borrow()))?;
+                },
+                &ElemContent::Comment(ref text) => {
+                    sink.write(XmlEvent::comment(text.borrow()))?;
+                },
             }
         }
 
@@ -261,6 +347,82 @@ impl<'a> Element<'a> {
         self.serialize(&mut writer)
     }
 
+    /// Writes this `Element` into the given stream, collecting every namespace used in the tree
+    /// into a single set of declarations on the root element instead of re-declaring a namespace
+    /// on every element that uses it.
+    ///
+    /// Any namespace that isn't already associated with an explicit prefix somewhere in the tree
+    /// is assigned a generated `ns0`, `ns1`, ... prefix. This is a convenience for
+    /// `into_stream_with_config` with `WriterConfig::new().collapse_namespaces(true)`.
+    pub fn into_stream_collapsed<W: Write>(&self, stream: W) -> writer::Result<()> {
+        self.into_stream_with_config(stream, WriterConfig::new().collapse_namespaces(true))
+    }
+
+    /// Writes this `Element` into the given stream using the given `WriterConfig`.
+    pub fn into_stream_with_config<W: Write>(&self, stream: W, config: WriterConfig) -> writer::Result<()> {
+        let mut writer = EventWriter::new(stream);
+
+        if config.collapse_namespaces {
+            let namespaces = collect_namespaces(self);
+            self.serialize_collapsed(&mut writer, &namespaces, true)
+        } else {
+            self.serialize(&mut writer)
+        }
+    }
+
+    /// Serializes this `Element` using a namespace table already resolved by `collect_namespaces`,
+    /// declaring every namespace in the table on the root element only.
+    fn serialize_collapsed<W: Write>(&self, sink: &mut EventWriter<W>, namespaces: &HashMap<String, String>, is_root: bool)
+        -> writer::Result<()>
+    {
+        let full_name = match &self.name.namespace {
+            &Some(ref ns) => format!("{}:{}", resolve_prefix(namespaces, ns.borrow()), self.name.local_name),
+            &None => self.name.local_name.to_string(),
+        };
+
+        let mut elem = XmlEvent::start_element(full_name.as_str());
+
+        if is_root {
+            for (ns, prefix) in namespaces {
+                elem = elem.ns(prefix.as_str(), ns.as_str());
+            }
+        }
+
+        let attr_names: Vec<String> = self.attributes.iter()
+            .map(|attr| match &attr.name.namespace {
+                &Some(ref ns) => format!("{}:{}", resolve_prefix(namespaces, ns.borrow()), attr.name.local_name),
+                &None => attr.name.local_name.to_string(),
+            })
+            .collect();
+
+        for (attr, full_name) in self.attributes.iter().zip(attr_names.iter()) {
+            elem = elem.attr(full_name.as_str(), attr.value.borrow());
+        }
+
+        sink.write(elem)?;
+
+        for item in &self.content {
+            match item {
+                &ElemContent::Text(ref text) => {
+                    sink.write(text.borrow())?;
+                },
+                &ElemContent::Child(ref child) => {
+                    child.serialize_collapsed(sink, namespaces, false)?;
+                },
+                &ElemContent::CData(ref text) => {
+                    sink.write(XmlEvent::cdata(text.borrow()))?;
+                },
+                &ElemContent::Comment(ref text) => {
+                    sink.write(XmlEvent::comment(text.borrow()))?;
+                },
+            }
+        }
+
+        sink.write(XmlEvent::end_element())?;
+
+        Ok(())
+    }
+
     ///Returns the first child element that matches the given predicate.
     pub fn first_child_where<'s, F: FnMut(&Element) -> bool>(&'s self, mut pred: F)
         -> Option<&'s Element<'a>>
@@ -275,6 +437,287 @@ impl<'a> Element<'a> {
 
         None
     }
+
+    /// Returns the descendant located by the given ElementTree-style path.
+    ///
+    /// A path is a `/`-separated list of segments, each matching one child by name. A segment
+    /// written as `{namespace}local` matches only that local name within that namespace URI; a
+    /// bare `local` segment matches that local name in any namespace. For example,
+    /// `"{http://schemas.xmlsoap.org/soap/envelope/}Body/{http://schemas.xmlsoap.org/soap/envelope/}Fault"`
+    /// finds the SOAP `Fault` child of the SOAP `Body` child of this element.
+    pub fn find(&self, path: &str) -> Option<&Element<'a>> {
+        self.find_segments(&split_path_segments(path))
+    }
+
+    /// Returns the descendant located by the given list of already-split path segments.
+    fn find_segments(&self, segments: &[&str]) -> Option<&Element<'a>> {
+        let mut current = self;
+
+        for segment in segments {
+            let (namespace, local) = parse_path_segment(segment);
+            current = current.first_child_where(|child| segment_matches(&child.name, namespace, local))?;
+        }
+
+        Some(current)
+    }
+
+    /// Returns every direct child matching the given namespace and local name, in document order.
+    fn children_matching<'s>(&'s self, namespace: Option<&str>, local: &str) -> Vec<&'s Element<'a>> {
+        self.content.iter()
+            .filter_map(|item| match item {
+                &ElemContent::Child(ref child) if segment_matches(&child.name, namespace, local) => Some(child),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns every descendant reachable by the given list of already-split path segments,
+    /// descending through every match at each step rather than just the first.
+    fn find_all_segments<'s>(&'s self, segments: &[&str]) -> Vec<&'s Element<'a>> {
+        let mut current = vec![self];
+
+        for segment in segments {
+            let (namespace, local) = parse_path_segment(segment);
+            current = current.into_iter()
+                .flat_map(|elem| elem.children_matching(namespace, local))
+                .collect();
+        }
+
+        current
+    }
+
+    /// Returns an iterator over every element matching the final segment of the given
+    /// ElementTree-style path.
+    ///
+    /// All but the last segment are resolved the same way, except that every intermediate element
+    /// matching a segment is descended into (not just the first), so a path like `a/b` yields a
+    /// `b` from every matching `a`, mirroring Python's `ElementTree.findall`.
+    pub fn find_all<'s>(&'s self, path: &str) -> impl Iterator<Item = &'s Element<'a>> {
+        let mut segments = split_path_segments(path);
+        let last = segments.pop().unwrap_or("");
+
+        let (namespace, local) = parse_path_segment(last);
+        let namespace = namespace.map(str::to_owned);
+        let local = local.to_owned();
+
+        self.find_all_segments(&segments).into_iter()
+            .flat_map(move |parent| parent.children_matching(namespace.as_ref().map(|ns| ns.as_str()), &local))
+    }
+
+    /// Concatenates this element's direct text content into a single string.
+    ///
+    /// Returns `None` if the element has no `Text` content of its own, e.g. if it only contains
+    /// child elements.
+    pub fn text(&self) -> Option<Cow<str>> {
+        let mut result: Option<Cow<str>> = None;
+
+        for item in &self.content {
+            if let &ElemContent::Text(ref text) = item {
+                result = Some(match result {
+                    Some(existing) => Cow::Owned(existing.into_owned() + text),
+                    None => text.clone(),
+                });
+            }
+        }
+
+        result
+    }
+}
+
+/// Drives the depth-based element stack shared by `Element::from_stream` and
+/// `ElementReader::read_subtree`.
+///
+/// `elem_stack` is treated as a strict LIFO: an `EndElement` always closes whatever element is on
+/// top of the stack (asserting that its name matches, for well-formedness), and `Characters`,
+/// `CData`, and `Comment` are pushed onto whatever element is currently open. This keeps document
+/// order intact, including mixed text and child content, even when sibling or ancestor elements
+/// share the same name.
+///
+/// Returns `Some(element)` when this call closes out the last element that was open on
+/// `elem_stack`, handing ownership of the finished element back to the caller. `elem_stack` must
+/// not be empty when an `EndElement` is passed in; callers are expected to stop pulling events
+/// once their own element's `EndElement` is reached rather than handing it to this function.
+fn close_out_stack(event: ReaderEvent, elem_stack: &mut Vec<Element<'static>>) -> Option<Element<'static>> {
+    match event {
+        ReaderEvent::StartElement { name, attributes, .. } => {
+            elem_stack.push(Element {
+                name: name.into(),
+                attributes: attributes.into_iter().map(Attribute::from).collect(),
+                content: vec![],
+            });
+            None
+        }
+        ReaderEvent::EndElement { name } => {
+            let end_name: Name = name.into();
+            let child = elem_stack.pop().expect("received EndElement with no open element");
+            assert!(child.name == end_name, "mismatched closing tag");
+
+            match elem_stack.last_mut() {
+                Some(head) => {
+                    head.push_child(child);
+                    None
+                },
+                None => Some(child),
+            }
+        }
+        ReaderEvent::Characters(text) => {
+            if let Some(head) = elem_stack.last_mut() {
+                head.push_text(text);
+            }
+            None
+        }
+        ReaderEvent::CData(text) => {
+            if let Some(head) = elem_stack.last_mut() {
+                head.push_cdata(text);
+            }
+            None
+        }
+        ReaderEvent::Comment(text) => {
+            if let Some(head) = elem_stack.last_mut() {
+                head.push_comment(trim_comment_padding(text));
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+/// Strips the single leading and trailing space that `xml-rs`'s writer always pads a comment's
+/// body with (`<!--text-->` is written as `<!-- text -->`), so that a comment read back after
+/// `Element::serialize` round-trips to the same content it started as.
+fn trim_comment_padding(text: String) -> String {
+    let mut body: &str = &text;
+    if body.starts_with(' ') {
+        body = &body[1..];
+    }
+    if body.ends_with(' ') {
+        body = &body[..body.len() - 1];
+    }
+    body.to_string()
+}
+
+/// Splits an ElementTree-style path into its `/`-separated segments, without breaking apart a
+/// `{namespace}` block even when the namespace URI itself contains a `/`.
+fn split_path_segments(path: &str) -> Vec<&str> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    let mut in_namespace = false;
+
+    for (i, c) in path.char_indices() {
+        match c {
+            '{' => in_namespace = true,
+            '}' => in_namespace = false,
+            '/' if !in_namespace => {
+                segments.push(&path[start..i]);
+                start = i + 1;
+            },
+            _ => {},
+        }
+    }
+    segments.push(&path[start..]);
+
+    segments
+}
+
+/// Splits a single ElementTree-style path segment into an optional namespace URI (from
+/// `{namespace}local` syntax) and the local name to match.
+fn parse_path_segment(segment: &str) -> (Option<&str>, &str) {
+    if segment.starts_with('{') {
+        if let Some(end) = segment.find('}') {
+            return (Some(&segment[1..end]), &segment[end + 1..]);
+        }
+    }
+
+    (None, segment)
+}
+
+/// Returns whether `name` matches the given local name and, if present, namespace URI. A `None`
+/// namespace matches any namespace, mirroring the "bare `local`" path segment syntax.
+fn segment_matches(name: &Name, namespace: Option<&str>, local: &str) -> bool {
+    if name.local_name.borrow() as &str != local {
+        return false;
+    }
+
+    match namespace {
+        Some(ns) => name.namespace.as_ref().map(|n| n.borrow() as &str) == Some(ns),
+        None => true,
+    }
+}
+
+/// Walks the given `Element` and every descendant, resolving a single prefix for each distinct
+/// namespace URI in use. A namespace that already has an explicit prefix somewhere in the tree
+/// keeps that prefix, unless an earlier namespace in the tree has already claimed the same prefix;
+/// any other namespace is assigned a generated `ns0`, `ns1`, ... prefix that doesn't collide with
+/// a prefix already in the table.
+fn collect_namespaces(elem: &Element) -> HashMap<String, String> {
+    let mut discovered = Vec::<(String, Option<String>)>::new();
+
+    fn record(name: &Name, discovered: &mut Vec<(String, Option<String>)>) {
+        let ns = match &name.namespace {
+            &Some(ref ns) => (ns.borrow() as &str).to_string(),
+            &None => return,
+        };
+
+        match discovered.iter_mut().find(|&&mut (ref uri, _)| *uri == ns) {
+            Some(&mut (_, ref mut prefix)) => {
+                if prefix.is_none() {
+                    *prefix = name.prefix.as_ref().map(|p| (p.borrow() as &str).to_string());
+                }
+            },
+            None => discovered.push((ns, name.prefix.as_ref().map(|p| (p.borrow() as &str).to_string()))),
+        }
+    }
+
+    fn walk(elem: &Element, discovered: &mut Vec<(String, Option<String>)>) {
+        record(&elem.name, discovered);
+
+        for attr in &elem.attributes {
+            record(&attr.name, discovered);
+        }
+
+        for item in &elem.content {
+            if let &ElemContent::Child(ref child) = item {
+                walk(child, discovered);
+            }
+        }
+    }
+
+    walk(elem, &mut discovered);
+
+    let mut used_prefixes = HashSet::<String>::new();
+    let mut next_generated = 0;
+    let mut resolved = HashMap::new();
+
+    for (ns, prefix) in discovered {
+        // An explicit prefix only survives if no earlier namespace in the tree has already
+        // claimed it; a prefix reused across two different URIs (e.g. nested scopes that both
+        // declare `p`) would otherwise collide into a single `xmlns:p` at the root and silently
+        // reassign one URI's elements to the other.
+        let prefix = match prefix {
+            Some(prefix) if !used_prefixes.contains(&prefix) => prefix,
+            _ => {
+                let mut candidate = format!("ns{}", next_generated);
+                while used_prefixes.contains(&candidate) {
+                    next_generated += 1;
+                    candidate = format!("ns{}", next_generated);
+                }
+                next_generated += 1;
+                candidate
+            }
+        };
+
+        used_prefixes.insert(prefix.clone());
+        resolved.insert(ns, prefix);
+    }
+
+    resolved
+}
+
+/// Looks up the prefix resolved for the given namespace URI by `collect_namespaces`.
+fn resolve_prefix<'a>(namespaces: &'a HashMap<String, String>, ns: &str) -> &'a str {
+    namespaces.get(ns)
+        .map(|p| p.as_str())
+        .expect("collect_namespaces should have recorded every namespace in the tree")
 }
 
 /// Display impl that formats this `Element` into XML and writes it to the given writer.
@@ -299,3 +742,320 @@ impl<'a> fmt::Display for Element<'a> {
         f.write_str(&result)
     }
 }
+
+/// A single event read lazily from an `ElementReader`.
+#[derive(Debug)]
+pub enum Event<'a> {
+    /// The start of an element, with its resolved name and attributes.
+    Start {
+        /// The element's name.
+        name: Name<'a>,
+        /// The element's attributes.
+        attributes: Vec<Attribute<'a>>,
+    },
+    /// The end of an element.
+    End {
+        /// The element's name.
+        name: Name<'a>,
+    },
+    /// Text content.
+    Text(Cow<'a, str>),
+    /// A `CDATA` section.
+    CData(Cow<'a, str>),
+    /// A comment.
+    Comment(Cow<'a, str>),
+}
+
+/// A pull-style reader that yields `Event`s lazily from the underlying stream, rather than
+/// eagerly materializing an entire `Element` tree the way `Element::from_stream` does.
+///
+/// This is useful for scanning a large document for a handful of fields without paying to
+/// allocate the parts the caller doesn't need. Callers that do want a subtree once they've found
+/// it can pull one out with `read_subtree`.
+pub struct ElementReader<R: Read> {
+    reader: EventReader<R>,
+}
+
+impl<R: Read> ElementReader<R> {
+    /// Wraps the given stream in an `ElementReader`.
+    pub fn new(stream: R) -> ElementReader<R> {
+        let config = ParserConfig::new().ignore_comments(false);
+        ElementReader {
+            reader: EventReader::new_with_config(stream, config),
+        }
+    }
+
+    /// Materializes the next complete child element from the stream as an `Element`.
+    ///
+    /// Returns `None` if the stream ends, or closes the current element, before a `StartElement`
+    /// is found, i.e. there is no further child to read here.
+    pub fn read_subtree(&mut self) -> Option<reader::Result<Element<'static>>> {
+        let mut elem_stack = Vec::<Element<'static>>::new();
+
+        loop {
+            let event = match self.reader.next() {
+                Ok(event) => event,
+                Err(e) => return Some(Err(e)),
+            };
+
+            match event {
+                ReaderEvent::EndDocument => return None,
+                // A closing tag with nothing of our own open means we've hit the end of our
+                // caller's element, i.e. there is no further child to read here.
+                ReaderEvent::EndElement { .. } if elem_stack.is_empty() => return None,
+                event => {
+                    if let Some(elem) = close_out_stack(event, &mut elem_stack) {
+                        return Some(Ok(elem));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Yields the element, text, `CDATA`, and comment events in the stream in document order,
+/// resolving namespaces as it goes. Whitespace-only text, processing instructions, and the
+/// document start are skipped.
+impl<R: Read> Iterator for ElementReader<R> {
+    type Item = reader::Result<Event<'static>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.reader.next() {
+                Ok(ReaderEvent::EndDocument) => return None,
+                Ok(ReaderEvent::StartElement { name, attributes, .. }) => {
+                    return Some(Ok(Event::Start {
+                        name: name.into(),
+                        attributes: attributes.into_iter().map(Attribute::from).collect(),
+                    }));
+                },
+                Ok(ReaderEvent::EndElement { name }) => {
+                    return Some(Ok(Event::End { name: name.into() }));
+                },
+                Ok(ReaderEvent::Characters(text)) => return Some(Ok(Event::Text(text.into()))),
+                Ok(ReaderEvent::CData(text)) => return Some(Ok(Event::CData(text.into()))),
+                Ok(ReaderEvent::Comment(text)) => {
+                    return Some(Ok(Event::Comment(trim_comment_padding(text).into())));
+                },
+                Ok(_) => continue,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_nested_same_named_elements() {
+        let doc = "<item><item>inner</item></item>";
+        let root = Element::from_string(doc).unwrap();
+
+        assert_eq!(root.name.local_name, "item");
+        assert_eq!(root.content.len(), 1);
+
+        let inner = match &root.content[0] {
+            &ElemContent::Child(ref inner) => inner,
+            other => panic!("expected a single child element, got {:?}", other),
+        };
+        assert_eq!(inner.name.local_name, "item");
+        assert_eq!(inner.text().as_ref().map(|t| t.as_ref()), Some("inner"));
+
+        let mut buf = Vec::new();
+        root.into_stream(&mut buf).unwrap();
+        let reparsed = Element::from_string(&String::from_utf8(buf).unwrap()).unwrap();
+
+        assert_eq!(reparsed.content.len(), 1);
+        let reparsed_inner = match &reparsed.content[0] {
+            &ElemContent::Child(ref inner) => inner,
+            other => panic!("expected a single child element after round-trip, got {:?}", other),
+        };
+        assert_eq!(reparsed_inner.text().as_ref().map(|t| t.as_ref()), Some("inner"));
+    }
+
+    #[test]
+    fn collapse_reassigns_a_reused_prefix_bound_to_different_namespaces() {
+        let doc = r#"<r xmlns:p="urn:a"><p:x>A</p:x><c xmlns:p="urn:b"><p:y>B</p:y></c></r>"#;
+        let root = Element::from_string(doc).unwrap();
+
+        let mut buf = Vec::new();
+        root.into_stream_collapsed(&mut buf).unwrap();
+        let reparsed = Element::from_string(&String::from_utf8(buf).unwrap()).unwrap();
+
+        let x = reparsed.find("x").expect("should find `x` under any namespace");
+        assert_eq!(x.name.namespace.as_ref().map(|ns| ns.as_ref()), Some("urn:a"));
+
+        let y = reparsed.find("c/y").expect("should find `y` under any namespace");
+        assert_eq!(y.name.namespace.as_ref().map(|ns| ns.as_ref()), Some("urn:b"));
+    }
+
+    #[test]
+    fn finds_namespaced_path_with_slashes_in_uri() {
+        let doc = r#"<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+            <soap:Body>
+                <soap:Fault>boom</soap:Fault>
+                <soap:Fault>bang</soap:Fault>
+            </soap:Body>
+        </soap:Envelope>"#;
+        let root = Element::from_string(doc).unwrap();
+
+        let fault = root
+            .find("{http://schemas.xmlsoap.org/soap/envelope/}Body/{http://schemas.xmlsoap.org/soap/envelope/}Fault")
+            .expect("should find the Fault element despite the slashes in its namespace URI");
+        assert_eq!(fault.text().as_ref().map(|t| t.as_ref()), Some("boom"));
+
+        let faults: Vec<_> = root
+            .find_all("{http://schemas.xmlsoap.org/soap/envelope/}Body/{http://schemas.xmlsoap.org/soap/envelope/}Fault")
+            .collect();
+        assert_eq!(faults.len(), 2);
+        assert_eq!(faults[1].text().as_ref().map(|t| t.as_ref()), Some("bang"));
+    }
+
+    #[test]
+    fn find_all_descends_every_matching_intermediate() {
+        let doc = "<r><a><b>1</b></a><a><b>2</b></a></r>";
+        let root = Element::from_string(doc).unwrap();
+
+        let found: Vec<_> = root.find_all("a/b")
+            .map(|elem| elem.text().as_ref().map(|t| t.to_string()))
+            .collect();
+        assert_eq!(found, vec![Some("1".to_string()), Some("2".to_string())]);
+    }
+
+    #[test]
+    fn round_trips_attributes() {
+        let mut root = Element::new_no_ns("Body");
+        root.push_attr(
+            Name::new("type", "http://www.w3.org/2001/XMLSchema-instance", "xsi"),
+            "xsd:string",
+        );
+        root.push_attr(Name::new_no_ns("plain"), "value");
+
+        assert_eq!(
+            root.get_attr("type", Some("http://www.w3.org/2001/XMLSchema-instance")),
+            Some("xsd:string")
+        );
+        assert_eq!(root.get_attr("plain", None), Some("value"));
+
+        let mut buf = Vec::new();
+        root.into_stream(&mut buf).unwrap();
+        let reparsed = Element::from_string(&String::from_utf8(buf).unwrap()).unwrap();
+
+        assert_eq!(
+            reparsed.get_attr("type", Some("http://www.w3.org/2001/XMLSchema-instance")),
+            Some("xsd:string")
+        );
+        assert_eq!(reparsed.get_attr("plain", None), Some("value"));
+    }
+
+    #[test]
+    fn preserves_mixed_content_order() {
+        let doc = "<a>before<b/>after</a>";
+        let root = Element::from_string(doc).unwrap();
+
+        assert_eq!(root.content.len(), 3);
+
+        match &root.content[0] {
+            &ElemContent::Text(ref text) => assert_eq!(text.as_ref(), "before"),
+            other => panic!("expected leading text, got {:?}", other),
+        }
+        match &root.content[1] {
+            &ElemContent::Child(ref child) => assert_eq!(child.name.local_name, "b"),
+            other => panic!("expected a child element, got {:?}", other),
+        }
+        match &root.content[2] {
+            &ElemContent::Text(ref text) => assert_eq!(text.as_ref(), "after"),
+            other => panic!("expected trailing text, got {:?}", other),
+        }
+
+        let mut buf = Vec::new();
+        root.into_stream(&mut buf).unwrap();
+        let reparsed = Element::from_string(&String::from_utf8(buf).unwrap()).unwrap();
+
+        assert_eq!(reparsed.content.len(), 3);
+    }
+
+    #[test]
+    fn round_trips_cdata_and_comments() {
+        let doc = "<a><!--a comment--><![CDATA[<raw markup>]]></a>";
+        let root = Element::from_string(doc).unwrap();
+
+        assert_eq!(root.content.len(), 2);
+        match &root.content[0] {
+            &ElemContent::Comment(ref text) => assert_eq!(text.as_ref(), "a comment"),
+            other => panic!("expected a comment, got {:?}", other),
+        }
+        match &root.content[1] {
+            &ElemContent::CData(ref text) => assert_eq!(text.as_ref(), "<raw markup>"),
+            other => panic!("expected a CDATA section, got {:?}", other),
+        }
+
+        let mut buf = Vec::new();
+        root.into_stream(&mut buf).unwrap();
+        let reparsed = Element::from_string(&String::from_utf8(buf).unwrap()).unwrap();
+
+        assert_eq!(reparsed.content.len(), 2);
+        match &reparsed.content[0] {
+            &ElemContent::Comment(ref text) => assert_eq!(text.as_ref(), "a comment"),
+            other => panic!("expected a comment after round-trip, got {:?}", other),
+        }
+        match &reparsed.content[1] {
+            &ElemContent::CData(ref text) => assert_eq!(text.as_ref(), "<raw markup>"),
+            other => panic!("expected a CDATA section after round-trip, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn read_subtree_respects_same_named_nesting() {
+        let doc = "<item><item>inner</item><sibling/></item>";
+        let mut reader = ElementReader::new(doc.as_bytes());
+
+        let outer = reader.read_subtree().unwrap().unwrap();
+        assert_eq!(outer.name.local_name, "item");
+        assert_eq!(outer.content.len(), 2);
+
+        let inner = match &outer.content[0] {
+            &ElemContent::Child(ref inner) => inner,
+            other => panic!("expected the nested `item`, got {:?}", other),
+        };
+        assert_eq!(inner.name.local_name, "item");
+        assert_eq!(inner.text().as_ref().map(|t| t.as_ref()), Some("inner"));
+
+        match &outer.content[1] {
+            &ElemContent::Child(ref sibling) => assert_eq!(sibling.name.local_name, "sibling"),
+            other => panic!("expected `sibling`, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn drives_the_pull_event_iterator() {
+        let doc = "<a>before<!--a comment--><![CDATA[raw]]></a>";
+        let reader = ElementReader::new(doc.as_bytes());
+
+        let events: Vec<Event> = reader.map(|event| event.unwrap()).collect();
+
+        assert_eq!(events.len(), 5);
+        match &events[0] {
+            &Event::Start { ref name, .. } => assert_eq!(name.local_name, "a"),
+            other => panic!("expected a Start event, got {:?}", other),
+        }
+        match &events[1] {
+            &Event::Text(ref text) => assert_eq!(text.as_ref(), "before"),
+            other => panic!("expected a Text event, got {:?}", other),
+        }
+        match &events[2] {
+            &Event::Comment(ref text) => assert_eq!(text.as_ref(), "a comment"),
+            other => panic!("expected a Comment event, got {:?}", other),
+        }
+        match &events[3] {
+            &Event::CData(ref text) => assert_eq!(text.as_ref(), "raw"),
+            other => panic!("expected a CData event, got {:?}", other),
+        }
+        match &events[4] {
+            &Event::End { ref name } => assert_eq!(name.local_name, "a"),
+            other => panic!("expected an End event, got {:?}", other),
+        }
+    }
+}